@@ -68,6 +68,42 @@ pub struct Options {
     )]
     pub accept_invalid_certs: bool,
 
+    #[structopt(
+        long = "sslmode",
+        name = "SSLMODE",
+        help = "libpq sslmode (e.g. disable, prefer, require, verify-ca, verify-full) used when building the connection URL from flags"
+    )]
+    pub sslmode: Option<String>,
+
+    #[structopt(
+        long = "target",
+        name = "TARGET",
+        help = "Restore the anonymized data directly into this target database URL instead of writing a dump file"
+    )]
+    pub target: Option<String>,
+
+    #[structopt(
+        short = "j",
+        long = "jobs",
+        help = "Number of tables to dump concurrently using a snapshot-synchronized connection pool",
+        default_value = "1"
+    )]
+    pub jobs: usize,
+
+    #[structopt(
+        long = "connect-retry-interval-ms",
+        help = "Initial backoff interval, in milliseconds, before retrying a transient connection error",
+        default_value = "500"
+    )]
+    pub connect_retry_interval_ms: u64,
+
+    #[structopt(
+        long = "connect-max-elapsed",
+        help = "Maximum total time, in seconds, to keep retrying transient connection errors before giving up",
+        default_value = "60"
+    )]
+    pub connect_max_elapsed: u64,
+
     #[structopt(
         name = "PG_DUMP_ARGS",
         help = "The remaining arguments are passed directly to `pg_dump` calls. You should add `--` before <DBNAME> in such cases"
@@ -77,14 +113,28 @@ pub struct Options {
 
 impl Options {
     pub fn database_url(&self) -> Result<Url> {
-        if let Ok(url) = Url::parse(self.database.as_str()) {
+        self.url_from(self.database.as_str())
+    }
+
+    pub fn target_url(&self) -> Result<Option<Url>> {
+        match self.target.as_deref().filter(|t| !t.is_empty()) {
+            None => Ok(None),
+            Some(target) => self.url_from(target).map(Some),
+        }
+    }
+
+    // Parses `s` as a full `postgres://` URL, or falls back to building one
+    // from the host/port/user/password flags (treating `s` as the database
+    // name). Shared by `database_url` and `target_url`.
+    fn url_from(&self, s: &str) -> Result<Url> {
+        if let Ok(url) = Url::parse(s) {
             if url.scheme() == "postgres" {
                 return Ok(url);
             } else {
                 return Err(anyhow!("Scheme url error"));
             }
         }
-        self.build_url(Some(self.database.to_string()).filter(|x| !x.is_empty()))
+        self.build_url(Some(s.to_string()).filter(|x| !x.is_empty()))
     }
 
     fn build_url(&self, override_db_name: Option<String>) -> Result<Url> {
@@ -105,6 +155,10 @@ impl Options {
 
         url.set_path(&db_name);
 
+        if let Some(sslmode) = self.sslmode.as_deref().filter(|s| !s.is_empty()) {
+            url.set_query(Some(&format!("sslmode={}", sslmode)));
+        }
+
         Ok(url)
     }
 }
@@ -127,6 +181,11 @@ mod tests {
             pg_dump_location: "pg_dump".to_string(),
             accept_invalid_hostnames: false,
             accept_invalid_certs: false,
+            sslmode: None,
+            target: None,
+            jobs: 1,
+            connect_retry_interval_ms: 500,
+            connect_max_elapsed: 60,
             pg_dump_args: vec![],
         };
 
@@ -148,6 +207,11 @@ mod tests {
             pg_dump_location: "pg_dump".to_string(),
             accept_invalid_hostnames: false,
             accept_invalid_certs: false,
+            sslmode: None,
+            target: None,
+            jobs: 1,
+            connect_retry_interval_ms: 500,
+            connect_max_elapsed: 60,
             pg_dump_args: vec![],
         };
 
@@ -174,6 +238,34 @@ mod tests {
         assert_eq!(cfg4.database_url().unwrap().to_string(), expected4);
     }
 
+    #[test]
+    fn build_url_with_sslmode() {
+        let cfg = Options {
+            database: String::default(),
+            config: "./config.yml".to_string(),
+            file: None,
+            db_name: "test".to_string(),
+            host: "hostname".to_string(),
+            port: None,
+            username: None,
+            password: None,
+            pg_dump_location: "pg_dump".to_string(),
+            accept_invalid_hostnames: false,
+            accept_invalid_certs: false,
+            sslmode: Some("require".to_string()),
+            target: None,
+            jobs: 1,
+            connect_retry_interval_ms: 500,
+            connect_max_elapsed: 60,
+            pg_dump_args: vec![],
+        };
+
+        assert_eq!(
+            cfg.database_url().unwrap().to_string(),
+            "postgres://hostname/test?sslmode=require"
+        );
+    }
+
     #[test]
     fn parse_args() {
         let cmd = vec![
@@ -3,16 +3,179 @@ use super::schema_inspector::PgSchemaInspector;
 use super::table::PgTable;
 use super::writer::DumpWriter;
 use crate::{Dumper, SchemaInspector, Table};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use backoff::{backoff::Backoff, ExponentialBackoffBuilder};
 use datanymizer_engine::{Engine, Filter, Settings, TableList};
 use indicatif::{HumanDuration, ProgressBar, ProgressStyle};
 use postgres::{Client, Transaction};
 use std::{
+    collections::VecDeque,
+    error::Error as _,
     io::{self, prelude::*},
     process::{self, Command},
-    time::Instant,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
 };
 
+/// Tuning for the exponential backoff applied to transient connection errors.
+///
+/// Only the I/O level `ConnectionRefused` / `ConnectionReset` /
+/// `ConnectionAborted` kinds are considered transient; everything else (auth
+/// failures, bad SQL, ...) is permanent and fails immediately.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub initial_interval: Duration,
+    pub max_elapsed: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_millis(500),
+            max_elapsed: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Runs `op`, retrying with randomized exponential backoff while it fails
+    /// with a transient connection error, until it succeeds or the max elapsed
+    /// time is exceeded.
+    fn retry<T, F>(&self, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> std::result::Result<T, postgres::Error>,
+    {
+        let mut backoff = ExponentialBackoffBuilder::new()
+            .with_initial_interval(self.initial_interval)
+            .with_multiplier(2.0)
+            .with_max_elapsed_time(Some(self.max_elapsed))
+            .build();
+
+        loop {
+            match op() {
+                Ok(value) => return Ok(value),
+                Err(err) if is_transient(&err) => match backoff.next_backoff() {
+                    Some(delay) => thread::sleep(delay),
+                    None => return Err(err.into()),
+                },
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+/// TLS transport settings, derived from `--accept_invalid_certs` /
+/// `--accept_invalid_hostnames`. The actual `sslmode` negotiation is driven by
+/// the `sslmode` parameter in the connection URL (parsed by `postgres`).
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    pub accept_invalid_certs: bool,
+    pub accept_invalid_hostnames: bool,
+}
+
+impl TlsConfig {
+    /// Builds a connector honoring the two "danger" flags. The resulting
+    /// connector can be shared across the coordinator and every worker
+    /// connection.
+    pub fn connector(&self) -> Result<postgres_native_tls::MakeTlsConnector> {
+        let connector = native_tls::TlsConnector::builder()
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .danger_accept_invalid_hostnames(self.accept_invalid_hostnames)
+            .build()?;
+        Ok(postgres_native_tls::MakeTlsConnector::new(connector))
+    }
+}
+
+/// Extracts the `sslmode` parameter from a connection URL, if present.
+fn sslmode_from_url(url: &str) -> Option<String> {
+    let query = url.split_once('?')?.1;
+    query.split('&').find_map(|pair| {
+        pair.strip_prefix("sslmode=")
+            .map(|value| value.to_string())
+    })
+}
+
+/// A connection error is transient only when its underlying I/O error is one of
+/// the connection-level kinds that typically resolve themselves on a retry.
+fn is_transient(err: &postgres::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+    while let Some(e) = source {
+        if let Some(io_err) = e.downcast_ref::<io::Error>() {
+            return matches!(
+                io_err.kind(),
+                io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+            );
+        }
+        source = e.source();
+    }
+
+    false
+}
+
+/// Establishes a `postgres::Client`, applying the configured TLS transport and
+/// retrying transient connection errors with exponential backoff. This is the
+/// single connect path shared by the source, target and parallel-worker
+/// connections.
+fn connect(url: &str, tls: &TlsConfig, retry: &RetryConfig) -> Result<Client> {
+    let connector = tls.connector()?;
+    retry.retry(|| Client::connect(url, connector.clone()))
+}
+
+/// Dumps a single table into an in-memory buffer instead of straight to the
+/// [`DumpWriter`]. Used by the parallel dumping path so the coordinator can
+/// concatenate per-table buffers back in the deterministic weight order. This
+/// mirrors [`PgDumper::dump_table`] but without the shared progress bar, which
+/// does not make sense across concurrent workers.
+fn dump_table_buffer(
+    engine: &Engine,
+    settings: &Settings,
+    table: &PgTable,
+    tr: &mut Transaction,
+) -> Result<Vec<u8>> {
+    let mut buf: Vec<u8> = Vec::new();
+    let cfg = settings.get_table(table.get_name().as_str());
+
+    buf.write_all(b"\n")?;
+    buf.write_all(table.query_from().as_bytes())?;
+    buf.write_all(b"\n")?;
+
+    let mut count: u64 = 0;
+    if let Some(transformed_query) = table.transformed_query_to(cfg, count) {
+        let reader = tr.copy_out(transformed_query.as_str())?;
+        for line in reader.lines() {
+            let row = PgRow::from_string_row(line?, table.clone());
+            let transformed = row.transform(engine)?;
+            buf.write_all(transformed.as_bytes())?;
+            buf.write_all(b"\n")?;
+
+            count += 1;
+        }
+    }
+
+    if let Some(untransformed_query) = table.untransformed_query_to(cfg, count) {
+        let reader = tr.copy_out(untransformed_query.as_str())?;
+        for line in reader.lines() {
+            buf.write_all(line?.as_bytes())?;
+            buf.write_all(b"\n")?;
+
+            count += 1;
+        }
+    }
+
+    buf.write_all(b"\\.\n")?;
+    for seq in &table.sequences {
+        let last_value: i64 = tr.query_one(seq.last_value_query().as_str(), &[])?.get(0);
+        buf.write_all(b"\n")?;
+        buf.write_all(seq.setval_query(last_value).as_bytes())?;
+        buf.write_all(b"\n")?;
+    }
+
+    Ok(buf)
+}
+
 pub struct PgDumper {
     schema_inspector: PgSchemaInspector,
     engine: Engine,
@@ -20,6 +183,10 @@ pub struct PgDumper {
     pg_dump_location: String,
     pg_dump_args: Vec<String>,
     progress_bar: ProgressBar,
+    retry: RetryConfig,
+    jobs: usize,
+    tls: TlsConfig,
+    target_client: Option<Client>,
 }
 
 impl PgDumper {
@@ -28,6 +195,10 @@ impl PgDumper {
         pg_dump_location: String,
         target: Option<String>,
         pg_dump_args: Vec<String>,
+        retry: RetryConfig,
+        jobs: usize,
+        tls: TlsConfig,
+        restore_target: Option<String>,
     ) -> Result<Self> {
         let dump_writer = DumpWriter::new(target)?;
         let pb: ProgressBar = if dump_writer.can_log_to_stdout() {
@@ -36,6 +207,13 @@ impl PgDumper {
             ProgressBar::hidden()
         };
 
+        // In restore mode the anonymized rows are streamed straight into this
+        // second ("target") database instead of being serialized to SQL.
+        let target_client = match restore_target {
+            Some(url) => Some(connect(url.as_str(), &tls, &retry)?),
+            None => None,
+        };
+
         Ok(Self {
             engine,
             dump_writer,
@@ -43,21 +221,62 @@ impl PgDumper {
             schema_inspector: PgSchemaInspector {},
             progress_bar: pb,
             pg_dump_args,
+            retry,
+            jobs: jobs.max(1),
+            tls,
+            target_client,
         })
     }
 
+    /// Opens the primary (source) connection used for the dump, applying the
+    /// same TLS transport and transient-error retry as the target and worker
+    /// connections.
+    pub fn connect_source(&self) -> Result<Client> {
+        connect(
+            self.engine.settings.source.get_database_url(),
+            &self.tls,
+            &self.retry,
+        )
+    }
+
+    /// Entry point used by the binary: opens the source connection through
+    /// [`connect_source`](Self::connect_source) — so even a plain serial,
+    /// file-output run goes through `RetryConfig` and `TlsConfig` — and then
+    /// drives the `pre_data` / `data` / `post_data` stages over it.
+    pub fn run(&mut self) -> Result<()> {
+        let mut connection = self.connect_source()?;
+        self.pre_data(&mut connection)?;
+        self.data(&mut connection)?;
+        self.post_data(&mut connection)?;
+        Ok(())
+    }
+
     fn run_pg_dump(&mut self, section: &str) -> Result<()> {
         let program = &self.pg_dump_location;
         let args = vec!["--section", section];
         let table_args = Self::table_args(&self.engine.settings.filter);
         let db_url = self.engine.settings.source.get_database_url();
 
-        let dump_output = Command::new(program)
+        let mut command = Command::new(program);
+        command
             .args(&self.pg_dump_args)
             .args(&args)
             .args(&table_args)
-            .arg(&db_url)
-            .output()?;
+            .arg(&db_url);
+        // libpq (and therefore pg_dump) picks up the transport from PGSSLMODE.
+        // An explicit URL `sslmode` is passed on, so the schema dump uses the
+        // same sslmode as the data connection. Without one, the
+        // `accept_invalid_*` flags map to `require` (encrypt without
+        // verification) for pg_dump only; the data connection then keeps the
+        // `postgres` crate's default `prefer`, with the non-verifying TLS
+        // connector.
+        if let Some(sslmode) = sslmode_from_url(&db_url) {
+            command.env("PGSSLMODE", sslmode);
+        } else if self.tls.accept_invalid_certs || self.tls.accept_invalid_hostnames {
+            command.env("PGSSLMODE", "require");
+        }
+
+        let dump_output = command.output()?;
         if !dump_output.status.success() {
             eprintln!(
                 "pg_dump error. Command:\n{} {} {}\nOutput:",
@@ -73,6 +292,14 @@ impl PgDumper {
             process::exit(1);
         }
 
+        // In restore mode the schema is applied directly to the target database
+        // rather than written to the dump file.
+        if let Some(target) = self.target_client.as_mut() {
+            let sql = String::from_utf8_lossy(&dump_output.stdout);
+            target.batch_execute(sql.as_ref())?;
+            return Ok(());
+        }
+
         self.dump_writer
             .write_all(&dump_output.stdout)
             .map_err(|e| e)
@@ -175,6 +402,176 @@ impl PgDumper {
 
         Ok(())
     }
+
+    // Streams the anonymized rows of a single table straight into the target
+    // database via `COPY ... FROM STDIN` instead of serializing them to SQL.
+    fn restore_table(&mut self, table: &PgTable, tr: &mut Transaction) -> Result<()> {
+        let settings = self.settings();
+        let started = Instant::now();
+
+        self.debug(format!("Restore table: {}", &table.get_full_name()));
+
+        let cfg = settings.get_table(table.get_name().as_str());
+        self.init_progress_bar(table.count_of_query_to(cfg), &table.get_full_name());
+
+        let target = self
+            .target_client
+            .as_mut()
+            .expect("restore mode without a target connection");
+        let mut writer = target.copy_in(table.query_from().as_str())?;
+
+        let mut count: u64 = 0;
+        if let Some(transformed_query) = table.transformed_query_to(cfg, count) {
+            let reader = tr.copy_out(transformed_query.as_str())?;
+            for line in reader.lines() {
+                self.progress_bar.inc(1);
+
+                let row = PgRow::from_string_row(line?, table.clone());
+                let transformed = row.transform(&self.engine)?;
+                writer.write_all(transformed.as_bytes())?;
+                writer.write_all(b"\n")?;
+
+                count += 1;
+            }
+        }
+
+        if let Some(untransformed_query) = table.untransformed_query_to(cfg, count) {
+            let reader = tr.copy_out(untransformed_query.as_str())?;
+            for line in reader.lines() {
+                self.progress_bar.inc(1);
+
+                writer.write_all(line?.as_bytes())?;
+                writer.write_all(b"\n")?;
+
+                count += 1;
+            }
+        }
+
+        writer.finish()?;
+
+        for seq in &table.sequences {
+            let last_value: i64 = tr.query_one(seq.last_value_query().as_str(), &[])?.get(0);
+            target.batch_execute(seq.setval_query(last_value).as_str())?;
+        }
+        // Release the target borrow before touching `&self` again.
+        drop(target);
+
+        self.progress_bar.finish();
+        self.progress_bar.reset();
+
+        let finished = started.elapsed();
+        self.debug(format!(
+            "[Restoring: {}] Finished in {}",
+            table.get_full_name(),
+            HumanDuration(finished),
+        ));
+
+        Ok(())
+    }
+
+    // Dumps `self.jobs` tables concurrently over a pool of connections that all
+    // share the coordinator's MVCC snapshot, so the result is identical to the
+    // serial dump. Workers transform rows into their own buffers and the
+    // coordinator writes them out in the original (weight) order to keep the
+    // output file deterministic.
+    fn data_parallel(
+        &mut self,
+        connection: &mut Client,
+        tables: Vec<(PgTable, i32)>,
+        settings: &Settings,
+    ) -> Result<()> {
+        // The coordinator transaction exports a snapshot and is held open for
+        // the whole dump so the workers' `SET TRANSACTION SNAPSHOT` stays valid.
+        let mut coordinator = connection
+            .build_transaction()
+            .isolation_level(postgres::IsolationLevel::RepeatableRead)
+            .read_only(true)
+            .start()?;
+        let snapshot_id: String = coordinator
+            .query_one("SELECT pg_export_snapshot()", &[])?
+            .get(0);
+
+        // Keep only the tables we actually dump, preserving the weight order so
+        // the buffer indexes line up with the concatenation order below.
+        let tables: Vec<PgTable> = tables
+            .into_iter()
+            .map(|(table, _weight)| table)
+            .filter(|table| self.filter_table(table.get_full_name(), &settings.filter))
+            .collect();
+
+        let queue: Arc<Mutex<VecDeque<(usize, PgTable)>>> =
+            Arc::new(Mutex::new(tables.iter().cloned().enumerate().collect()));
+        let results: Arc<Mutex<Vec<Option<Vec<u8>>>>> =
+            Arc::new(Mutex::new(vec![None; tables.len()]));
+
+        let db_url = Arc::new(settings.source.get_database_url().to_string());
+        let snapshot_id = Arc::new(snapshot_id);
+        let engine = Arc::new(self.engine.clone());
+        let settings = Arc::new(settings.clone());
+        let retry = Arc::new(self.retry.clone());
+        let tls = Arc::new(self.tls.clone());
+
+        let mut handles = Vec::with_capacity(self.jobs);
+        for _ in 0..self.jobs {
+            let queue = Arc::clone(&queue);
+            let results = Arc::clone(&results);
+            let db_url = Arc::clone(&db_url);
+            let snapshot_id = Arc::clone(&snapshot_id);
+            let engine = Arc::clone(&engine);
+            let settings = Arc::clone(&settings);
+            let retry = Arc::clone(&retry);
+            let tls = Arc::clone(&tls);
+
+            handles.push(thread::spawn(move || -> Result<()> {
+                let mut client = connect(db_url.as_str(), &tls, &retry)?;
+                let mut tr = client
+                    .build_transaction()
+                    .isolation_level(postgres::IsolationLevel::RepeatableRead)
+                    .read_only(true)
+                    .start()?;
+                tr.batch_execute(
+                    format!("SET TRANSACTION SNAPSHOT '{}'", snapshot_id).as_str(),
+                )?;
+
+                while let Some((ind, table)) = {
+                    let mut queue = queue.lock().unwrap();
+                    queue.pop_front()
+                } {
+                    // Each table gets its own clone of the base engine so the
+                    // transformer RNG starts from the same state no matter which
+                    // worker picks the table up or in what order. Sharing a
+                    // single engine across workers would interleave RNG
+                    // consumption and break seed reproducibility.
+                    let engine = (*engine).clone();
+                    let buf = dump_table_buffer(&engine, &settings, &table, &mut tr)?;
+                    results.lock().unwrap()[ind] = Some(buf);
+                }
+
+                Ok(())
+            }));
+        }
+
+        for handle in handles {
+            handle.join().expect("dump worker panicked")?;
+        }
+
+        // All buffers are materialized; the snapshot is no longer needed.
+        drop(coordinator);
+
+        let results = Arc::try_unwrap(results)
+            .expect("dangling worker reference")
+            .into_inner()
+            .unwrap();
+        for (table, buf) in tables.iter().zip(results) {
+            self.write_log(format!("Dump table: {}", table.get_full_name()))?;
+            if let Some(buf) = buf {
+                self.dump_writer.write_all(&buf)?;
+            }
+        }
+
+        self.write_log("End dumping data".into())?;
+        Ok(())
+    }
 }
 
 impl Dumper for PgDumper {
@@ -197,6 +594,22 @@ impl Dumper for PgDumper {
         tables.sort_by(|a, b| b.1.cmp(&a.1));
         let all_tables_count = tables.len();
 
+        let restore = self.target_client.is_some();
+
+        // Parallel dumping writes per-table buffers to the dump file and has no
+        // path that streams into the target connection, so silently ignoring
+        // --target here would produce a dump instead of a restore. Reject the
+        // combination explicitly.
+        if restore && self.jobs > 1 {
+            return Err(anyhow!(
+                "--jobs > 1 is not supported together with --target (restore mode)"
+            ));
+        }
+
+        if self.jobs > 1 {
+            return self.data_parallel(connection, tables, &settings);
+        }
+
         // In transaction
         let mut tr = connection.transaction()?;
         for (ind, (table, _weight)) in tables.iter().enumerate() {
@@ -208,7 +621,11 @@ impl Dumper for PgDumper {
             ));
 
             if self.filter_table(table.get_full_name(), &settings.filter) {
-                self.dump_table(table, &mut tr)?;
+                if restore {
+                    self.restore_table(table, &mut tr)?;
+                } else {
+                    self.dump_table(table, &mut tr)?;
+                }
             } else {
                 self.debug(format!("[Dumping: {}] --- SKIP ---", table.get_full_name()));
             }
@@ -0,0 +1,14 @@
+use super::{DE_DE, EN, FR_FR, IT_IT, JA_JP, PT_BR, ZH_TW};
+
+/// Marker trait for the locales `LocalizedFaker` can dispatch to. The `fake`
+/// crate already supplies the localized name, address and company data for its
+/// built-in locales, so an implementation carries no extra state.
+pub trait ExtData {}
+
+impl ExtData for EN {}
+impl ExtData for ZH_TW {}
+impl ExtData for FR_FR {}
+impl ExtData for DE_DE {}
+impl ExtData for PT_BR {}
+impl ExtData for JA_JP {}
+impl ExtData for IT_IT {}
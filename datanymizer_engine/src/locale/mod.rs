@@ -10,6 +10,16 @@ pub use ru::RU;
 pub type EN = fake::locales::EN;
 #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 pub type ZH_TW = fake::locales::ZH_TW;
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub type FR_FR = fake::locales::FR_FR;
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub type DE_DE = fake::locales::DE_DE;
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub type PT_BR = fake::locales::PT_BR;
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub type JA_JP = fake::locales::JA_JP;
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub type IT_IT = fake::locales::IT_IT;
 
 #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone)]
@@ -17,6 +27,11 @@ pub enum LocaleConfig {
     EN,
     RU,
     ZH_TW,
+    FR_FR,
+    DE_DE,
+    PT_BR,
+    JA_JP,
+    IT_IT,
 }
 
 impl Default for LocaleConfig {
@@ -39,6 +54,11 @@ pub trait LocalizedFaker<V>: Localized {
             LocaleConfig::EN => self.fake(EN {}),
             LocaleConfig::RU => self.fake(RU {}),
             LocaleConfig::ZH_TW => self.fake(ZH_TW {}),
+            LocaleConfig::FR_FR => self.fake(FR_FR {}),
+            LocaleConfig::DE_DE => self.fake(DE_DE {}),
+            LocaleConfig::PT_BR => self.fake(PT_BR {}),
+            LocaleConfig::JA_JP => self.fake(JA_JP {}),
+            LocaleConfig::IT_IT => self.fake(IT_IT {}),
         }
     }
 }
@@ -51,5 +71,8 @@ mod tests {
     fn deserialization() {
         let l: LocaleConfig = serde_yaml::from_str("RU").unwrap();
         assert_eq!(l, LocaleConfig::RU);
+
+        let l: LocaleConfig = serde_yaml::from_str("FR_FR").unwrap();
+        assert_eq!(l, LocaleConfig::FR_FR);
     }
 }
@@ -0,0 +1,9 @@
+use super::ExtData;
+
+/// Russian locale. The `fake` crate does not ship an `RU` locale, so this is a
+/// custom marker type that the faker generators dispatch on.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone)]
+pub struct RU;
+
+impl ExtData for RU {}